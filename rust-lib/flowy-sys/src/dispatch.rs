@@ -7,33 +7,148 @@ use crate::{
     util::tokio_default_runtime,
 };
 use derivative::*;
-use futures_core::future::BoxFuture;
+use futures_core::{future::BoxFuture, stream::Stream};
 use futures_util::task::Context;
 use lazy_static::lazy_static;
 use pin_project::pin_project;
 use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap},
     fmt::{Debug, Display},
     future::Future,
     hash::Hash,
-    sync::RwLock,
+    sync::{Arc, RwLock},
     thread::JoinHandle,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
     macros::support::{Pin, Poll},
+    sync::{mpsc, OwnedSemaphorePermit, Semaphore},
     task::JoinError,
 };
+use tracing::Instrument;
+
+/// Default [`ConcurrencyConfig::max_concurrent_requests`].
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 512;
 
 lazy_static! {
     pub static ref EVENT_DISPATCH: RwLock<Option<EventDispatch>> = RwLock::new(None);
 }
 
+/// Wraps a [`Service`] in another, composed around `DispatchService` in
+/// the order passed to [`EventDispatch::construct`].
+pub trait Layer: Send + Sync {
+    fn layer(&self, inner: BoxService) -> BoxService;
+}
+
+/// The error type flowing through the internal [`Service`] chain.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+pub type BoxService = Box<
+    dyn Service<
+            DispatchRequest,
+            Response = EventResponse,
+            Error = BoxError,
+            Future = BoxFuture<'static, Result<EventResponse, BoxError>>,
+        > + Send
+        + Sync,
+>;
+
+/// What [`EventDispatch`] does when a request can't immediately claim a
+/// concurrency permit (see [`ConcurrencyConfig`]).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BackpressureStrategy {
+    /// Await a permit, queueing the request until capacity frees up.
+    Wait,
+    /// Fail fast with a capacity `EventResponse` instead of queueing.
+    Shed,
+}
+
+/// Bounds how many requests [`EventDispatch`] runs at once; `per_event_limits`
+/// caps individual events independently of `max_concurrent_requests`.
+pub struct ConcurrencyConfig {
+    pub max_concurrent_requests: usize,
+    pub strategy: BackpressureStrategy,
+    pub per_event_limits: Vec<(Event, usize)>,
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            strategy: BackpressureStrategy::Wait,
+            per_event_limits: Vec::new(),
+        }
+    }
+}
+
+struct ConcurrencyLimiter {
+    strategy: BackpressureStrategy,
+    global: Arc<Semaphore>,
+    per_event: HashMap<Event, Arc<Semaphore>>,
+}
+
+impl ConcurrencyLimiter {
+    fn new(config: ConcurrencyConfig) -> Self {
+        let per_event = config
+            .per_event_limits
+            .into_iter()
+            .map(|(event, limit)| (event, Arc::new(Semaphore::new(limit))))
+            .collect();
+
+        Self {
+            strategy: config.strategy,
+            global: Arc::new(Semaphore::new(config.max_concurrent_requests)),
+            per_event,
+        }
+    }
+
+    async fn acquire(&self, event: &Event) -> Result<Vec<OwnedSemaphorePermit>, EventResponse> {
+        // Per-event first: a saturated per-event semaphore must reject (or
+        // queue) the request before it ever claims a global permit, or a
+        // burst on one event would hold the global pool hostage while it
+        // waits on its own per-event slot, starving unrelated events.
+        let mut semaphores = Vec::with_capacity(2);
+        if let Some(semaphore) = self.per_event.get(event) {
+            semaphores.push(semaphore.clone());
+        }
+        semaphores.push(self.global.clone());
+
+        let mut permits = Vec::with_capacity(semaphores.len());
+        for semaphore in semaphores {
+            let permit = match self.strategy {
+                BackpressureStrategy::Wait => semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("dispatch semaphore should never be closed"),
+                BackpressureStrategy::Shed => semaphore.try_acquire_owned().map_err(|_| {
+                    let msg = format!("{:?} is at capacity, dispatch shed the request", event);
+                    log::trace!("{}", msg);
+                    InternalError::new(msg).as_response()
+                })?,
+            };
+            permits.push(permit);
+        }
+
+        Ok(permits)
+    }
+}
+
 pub struct EventDispatch {
     module_map: ModuleMap,
     runtime: tokio::runtime::Runtime,
+    service: Arc<BoxService>,
+    limiter: Arc<ConcurrencyLimiter>,
+    scheduler: mpsc::UnboundedSender<ScheduledDispatch>,
 }
 
 impl EventDispatch {
-    pub fn construct<F>(module_factory: F)
+    /// Builds a standalone `EventDispatch`, owning its own `ModuleMap` and runtime.
+    pub fn construct<F>(
+        module_factory: F,
+        layers: Vec<Box<dyn Layer>>,
+        concurrency: ConcurrencyConfig,
+    ) -> EventDispatch
     where
         F: FnOnce() -> Vec<Module>,
     {
@@ -41,50 +156,325 @@ impl EventDispatch {
         log::debug!("{}", module_info(&modules));
         let module_map = as_module_map(modules);
         let runtime = tokio_default_runtime().unwrap();
-        let dispatch = EventDispatch {
+        let base: BoxService = Box::new(DispatchService {
+            module_map: module_map.clone(),
+        });
+        let service = Arc::new(
+            layers
+                .into_iter()
+                .rev()
+                .fold(base, |inner, layer| layer.layer(inner)),
+        );
+        let limiter = Arc::new(ConcurrencyLimiter::new(concurrency));
+        let scheduler = spawn_scheduler(&runtime, service.clone(), limiter.clone());
+
+        EventDispatch {
             module_map,
             runtime,
-        };
+            service,
+            limiter,
+            scheduler,
+        }
+    }
 
+    /// Like [`EventDispatch::construct`], but also installs the result as the
+    /// process-wide [`EVENT_DISPATCH`] default.
+    pub fn construct_default<F>(
+        module_factory: F,
+        layers: Vec<Box<dyn Layer>>,
+        concurrency: ConcurrencyConfig,
+    ) where
+        F: FnOnce() -> Vec<Module>,
+    {
+        let dispatch = EventDispatch::construct(module_factory, layers, concurrency);
         *(EVENT_DISPATCH.write().unwrap()) = Some(dispatch);
     }
 
-    pub fn async_send(request: DispatchRequest) -> DispatchFuture {
-        match EVENT_DISPATCH.read() {
-            Ok(dispatch) => {
-                let dispatch = dispatch.as_ref().unwrap();
-                let module_map = dispatch.module_map.clone();
-                let service = Box::new(DispatchService { module_map });
-                log::trace!("{}: dispatch {:?} to runtime", &request.id, &request.event);
-                let join_handle = dispatch.runtime.spawn(async move {
-                    service
-                        .call(request)
-                        .await
-                        .unwrap_or_else(|e| InternalError::new(format!("{:?}", e)).as_response())
-                });
-
-                DispatchFuture {
-                    fut: Box::pin(async move {
-                        join_handle.await.unwrap_or_else(|e| {
-                            InternalError::new(format!("Dispatch join error: {:?}", e))
-                                .as_response()
-                        })
-                    }),
-                }
+    /// Enqueues `request` to run after `delay` with [`RetryPolicy::default()`].
+    pub fn schedule(&self, request: DispatchRequest, delay: Duration) {
+        self.schedule_with_retry(request, delay, RetryPolicy::default())
+    }
+
+    /// Enqueues `request` to run after `delay`, retrying on failure with
+    /// exponential backoff until `retry.max_attempts` is reached.
+    pub fn schedule_with_retry(
+        &self,
+        request: DispatchRequest,
+        delay: Duration,
+        retry: RetryPolicy,
+    ) {
+        let DispatchRequest {
+            event,
+            payload,
+            callback,
+            ..
+        } = request;
+        let scheduled = ScheduledDispatch {
+            event,
+            payload,
+            callback,
+            attempt: 0,
+            due: Instant::now() + delay,
+            retry,
+        };
+        if self.scheduler.send(scheduled).is_err() {
+            log::error!("Dispatch scheduler is gone, dropping scheduled event");
+        }
+    }
+
+    pub fn async_send(&self, request: DispatchRequest) -> DispatchFuture {
+        let service = self.service.clone();
+        let limiter = self.limiter.clone();
+        let event = request.event.clone();
+        log::trace!("{}: dispatch {:?} to runtime", &request.id, &request.event);
+        let join_handle = self.runtime.spawn(async move {
+            let _permits = match limiter.acquire(&event).await {
+                Ok(permits) => permits,
+                Err(response) => return response,
+            };
+            service
+                .call(request)
+                .await
+                .unwrap_or_else(box_error_into_response)
+        });
+
+        DispatchFuture {
+            fut: Box::pin(async move {
+                join_handle.await.unwrap_or_else(|e| {
+                    InternalError::new(format!("Dispatch join error: {:?}", e)).as_response()
+                })
+            }),
+        }
+    }
+
+    pub fn sync_send(&self, request: DispatchRequest) -> EventResponse {
+        futures::executor::block_on(async { self.async_send(request).await })
+    }
+
+    /// Like [`EventDispatch::async_send`], but returns a [`Stream`] of
+    /// [`EventResponse`] chunks instead of waiting for a single one.
+    pub fn async_send_stream(&self, mut request: DispatchRequest) -> EventResponseStream {
+        let (tx, rx) = mpsc::unbounded_channel();
+        request.stream_sink = Some(tx);
+        let _ = self.async_send(request);
+        EventResponseStream { rx }
+    }
+}
+
+/// Convenience wrapper around the process-wide [`EVENT_DISPATCH`] default.
+pub fn async_send(request: DispatchRequest) -> DispatchFuture {
+    match EVENT_DISPATCH.read() {
+        Ok(dispatch) => match dispatch.as_ref() {
+            Some(dispatch) => dispatch.async_send(request),
+            None => DispatchFuture {
+                fut: Box::pin(async {
+                    InternalError::new("EventDispatch default instance is not constructed")
+                        .as_response()
+                }),
             },
+        },
+        Err(e) => {
+            let msg = format!("Dispatch runtime error: {:?}", e);
+            log::trace!("{}", msg);
+            DispatchFuture {
+                fut: Box::pin(async { InternalError::new(msg).as_response() }),
+            }
+        },
+    }
+}
+
+pub fn sync_send(request: DispatchRequest) -> EventResponse {
+    futures::executor::block_on(async { async_send(request).await })
+}
+
+pub fn async_send_stream(mut request: DispatchRequest) -> EventResponseStream {
+    let (tx, rx) = mpsc::unbounded_channel();
+    request.stream_sink = Some(tx);
+    let _ = async_send(request);
+    EventResponseStream { rx }
+}
+
+pub fn schedule(request: DispatchRequest, delay: Duration) {
+    schedule_with_retry(request, delay, RetryPolicy::default())
+}
+
+pub fn schedule_with_retry(request: DispatchRequest, delay: Duration, retry: RetryPolicy) {
+    match EVENT_DISPATCH.read() {
+        Ok(dispatch) => match dispatch.as_ref() {
+            Some(dispatch) => dispatch.schedule_with_retry(request, delay, retry),
+            None => log::error!("EventDispatch default instance is not constructed"),
+        },
+        Err(e) => log::error!("Dispatch runtime error: {:?}", e),
+    }
+}
+
+/// Governs how [`EventDispatch::schedule_with_retry`] retries a handler
+/// that resolves to an error [`EventResponse`].
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(60),
+            jitter: true,
+        }
+    }
+}
 
-            Err(e) => {
-                let msg = format!("Dispatch runtime error: {:?}", e);
-                log::trace!("{}", msg);
-                DispatchFuture {
-                    fut: Box::pin(async { InternalError::new(msg).as_response() }),
+struct ScheduledDispatch {
+    event: Event,
+    payload: Payload,
+    callback: Option<BoxFutureCallback>,
+    attempt: u32,
+    due: Instant,
+    retry: RetryPolicy,
+}
+
+struct DueDispatch(ScheduledDispatch);
+
+impl PartialEq for DueDispatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.due == other.0.due
+    }
+}
+
+impl Eq for DueDispatch {}
+
+impl PartialOrd for DueDispatch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DueDispatch {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.due.cmp(&other.0.due)
+    }
+}
+
+/// Spawns the worker backing [`EventDispatch::schedule`] and returns the
+/// channel used to hand it new/retried work.
+fn spawn_scheduler(
+    runtime: &tokio::runtime::Runtime,
+    service: Arc<BoxService>,
+    limiter: Arc<ConcurrencyLimiter>,
+) -> mpsc::UnboundedSender<ScheduledDispatch> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    runtime.spawn(scheduler_loop(rx, service, limiter, tx.clone()));
+    tx
+}
+
+async fn scheduler_loop(
+    mut incoming: mpsc::UnboundedReceiver<ScheduledDispatch>,
+    service: Arc<BoxService>,
+    limiter: Arc<ConcurrencyLimiter>,
+    resubmit: mpsc::UnboundedSender<ScheduledDispatch>,
+) {
+    let mut pending: BinaryHeap<Reverse<DueDispatch>> = BinaryHeap::new();
+    loop {
+        let next_due = pending.peek().map(|Reverse(d)| d.0.due);
+        tokio::select! {
+            received = incoming.recv() => {
+                match received {
+                    Some(scheduled) => pending.push(Reverse(DueDispatch(scheduled))),
+                    None => break,
+                }
+            },
+            _ = sleep_until_due(next_due) => {
+                while matches!(pending.peek(), Some(Reverse(d)) if d.0.due <= Instant::now()) {
+                    let Reverse(DueDispatch(scheduled)) = pending.pop().unwrap();
+                    tokio::spawn(run_scheduled(
+                        scheduled,
+                        service.clone(),
+                        limiter.clone(),
+                        resubmit.clone(),
+                    ));
                 }
             },
         }
     }
+}
+
+async fn sleep_until_due(due: Option<Instant>) {
+    match due {
+        Some(instant) => tokio::time::sleep_until(tokio::time::Instant::from_std(instant)).await,
+        None => futures::future::pending::<()>().await,
+    }
+}
+
+async fn run_scheduled(
+    mut scheduled: ScheduledDispatch,
+    service: Arc<BoxService>,
+    limiter: Arc<ConcurrencyLimiter>,
+    resubmit: mpsc::UnboundedSender<ScheduledDispatch>,
+) {
+    let response = match limiter.acquire(&scheduled.event).await {
+        Ok(_permits) => {
+            let request =
+                DispatchRequest::new(scheduled.event.clone()).payload(scheduled.payload.clone());
+            service.call(request).await.unwrap_or_else(box_error_into_response)
+        },
+        Err(response) => response,
+    };
+
+    let out_of_attempts = scheduled.attempt + 1 >= scheduled.retry.max_attempts;
+    if !response.is_err() || out_of_attempts {
+        if let Some(callback) = scheduled.callback.take() {
+            callback(response).await;
+        }
+        return;
+    }
+
+    scheduled.attempt += 1;
+    scheduled.due = Instant::now() + backoff_delay(&scheduled.retry, scheduled.attempt);
+    if resubmit.send(scheduled).is_err() {
+        log::error!("Dispatch scheduler is gone, dropping retry");
+    }
+}
+
+fn backoff_delay(retry: &RetryPolicy, attempt: u32) -> Duration {
+    let factor = 2u32.checked_pow(attempt.min(20)).unwrap_or(u32::MAX);
+    let delay = retry
+        .base_delay
+        .checked_mul(factor)
+        .unwrap_or(retry.max_delay)
+        .min(retry.max_delay);
+
+    if retry.jitter {
+        jitter(delay)
+    } else {
+        delay
+    }
+}
+
+/// Scales `delay` by a pseudo-random factor in `0.5..=1.0` so retries
+/// from many requests don't all wake up in lockstep.
+fn jitter(delay: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let ratio = 0.5 + (nanos % 1000) as f64 / 2000.0;
+    delay.mul_f64(ratio)
+}
+
+pub struct EventResponseStream {
+    rx: mpsc::UnboundedReceiver<EventResponse>,
+}
 
-    pub fn sync_send(request: DispatchRequest) -> EventResponse {
-        futures::executor::block_on(async { EventDispatch::async_send(request).await })
+impl Stream for EventResponseStream {
+    type Item = EventResponse;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
     }
 }
 
@@ -116,6 +506,10 @@ pub struct DispatchRequest {
     pub payload: Payload,
     #[derivative(Debug = "ignore")]
     pub callback: Option<BoxFutureCallback>,
+    #[derivative(Debug = "ignore")]
+    stream_sink: Option<mpsc::UnboundedSender<EventResponse>>,
+    #[derivative(Debug = "ignore")]
+    span: Option<tracing::Span>,
 }
 
 impl DispatchRequest {
@@ -128,6 +522,8 @@ impl DispatchRequest {
             event: event.into(),
             id: uuid::Uuid::new_v4().to_string(),
             callback: None,
+            stream_sink: None,
+            span: None,
         }
     }
 
@@ -141,15 +537,36 @@ impl DispatchRequest {
         self
     }
 
+    /// Attaches the caller's [`tracing::Span`] so module dispatch opens a
+    /// connected child span instead of a disjoint one.
+    pub fn span(mut self, span: tracing::Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Set when the request was dispatched through [`EventDispatch::async_send_stream`].
+    pub fn stream_sink(&self) -> Option<mpsc::UnboundedSender<EventResponse>> {
+        self.stream_sink.clone()
+    }
+
+    pub(crate) fn request_span(&self) -> Option<tracing::Span> {
+        self.span.clone()
+    }
+
     pub(crate) fn into_parts(self) -> (ModuleRequest, Option<BoxFutureCallback>) {
         let DispatchRequest {
             event,
             payload,
             id,
             callback,
+            stream_sink,
+            span: _,
         } = self;
 
-        (ModuleRequest::new(event.clone(), id, payload), callback)
+        let mut module_request = ModuleRequest::new(event.clone(), id, payload);
+        module_request.stream_sink = stream_sink;
+
+        (module_request, callback)
     }
 }
 
@@ -159,7 +576,7 @@ pub(crate) struct DispatchService {
 
 impl Service<DispatchRequest> for DispatchService {
     type Response = EventResponse;
-    type Error = SystemError;
+    type Error = BoxError;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     #[cfg_attr(
@@ -172,11 +589,22 @@ impl Service<DispatchRequest> for DispatchService {
     )]
     fn call(&self, dispatch_request: DispatchRequest) -> Self::Future {
         let module_map = self.module_map.clone();
+        let stream_sink = dispatch_request.stream_sink();
+        let request_span = dispatch_request.request_span();
+        let parent_span = request_span.clone();
         let (request, callback) = dispatch_request.into_parts();
-        Box::pin(async move {
+        let fut = async move {
             let result = {
                 match module_map.get(&request.event()) {
                     Some(module) => {
+                        let module_span = tracing::span!(
+                            parent: parent_span.as_ref(),
+                            tracing::Level::DEBUG,
+                            "module_dispatch",
+                            module = %module.name,
+                            event = ?request.event(),
+                            request.id = %request.id(),
+                        );
                         let fut = module.new_service(());
                         log::trace!(
                             "{}: handle event: {:?} by {}",
@@ -184,8 +612,12 @@ impl Service<DispatchRequest> for DispatchService {
                             request.event(),
                             module.name
                         );
-                        let service_fut = fut.await?.call(request);
-                        service_fut.await
+                        async move {
+                            let service_fut = fut.await.map_err(Into::into)?.call(request);
+                            service_fut.await.map_err(Into::into)
+                        }
+                        .instrument(module_span)
+                        .await
                     },
                     None => {
                         let msg = format!(
@@ -198,14 +630,31 @@ impl Service<DispatchRequest> for DispatchService {
                 }
             };
 
-            let response = result.unwrap_or_else(|e| e.into());
+            let response = result.unwrap_or_else(box_error_into_response);
             log::trace!("Dispatch result: {:?}", response);
+            if let Some(sink) = stream_sink {
+                let _ = sink.send(response.clone());
+            }
             if let Some(callback) = callback {
                 callback(response.clone()).await;
             }
 
             Ok(response)
-        })
+        };
+
+        match request_span {
+            Some(span) => Box::pin(fut.instrument(span)),
+            None => Box::pin(fut),
+        }
+    }
+}
+
+/// Collapses a [`BoxError`] into an [`EventResponse`], preserving a
+/// [`SystemError`]'s structured code where one downcasts cleanly.
+fn box_error_into_response(error: BoxError) -> EventResponse {
+    match error.downcast::<SystemError>() {
+        Ok(system_error) => (*system_error).into(),
+        Err(error) => InternalError::new(format!("{:?}", error)).as_response(),
     }
 }
 
@@ -216,3 +665,88 @@ fn module_info(modules: &Vec<Module>) -> String {
     }
     info
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_delay_doubles_and_caps_at_max_delay() {
+        let retry = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: false,
+        };
+
+        assert_eq!(backoff_delay(&retry, 0), Duration::from_millis(100));
+        assert_eq!(backoff_delay(&retry, 1), Duration::from_millis(200));
+        assert_eq!(backoff_delay(&retry, 2), Duration::from_millis(400));
+        assert_eq!(backoff_delay(&retry, 10), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_scales_delay_into_half_to_full_range() {
+        let delay = Duration::from_millis(1000);
+        let jittered = jitter(delay);
+
+        assert!(jittered >= delay.mul_f64(0.5));
+        assert!(jittered <= delay);
+    }
+
+    #[tokio::test]
+    async fn acquire_claims_per_event_permit_before_global() {
+        let event = Event::from("test_event");
+        let limiter = Arc::new(ConcurrencyLimiter::new(ConcurrencyConfig {
+            max_concurrent_requests: 4,
+            strategy: BackpressureStrategy::Wait,
+            per_event_limits: vec![(event.clone(), 1)],
+        }));
+
+        // Saturate the per-event semaphore so a second acquire has to wait on it.
+        let held = limiter.acquire(&event).await.unwrap();
+        assert_eq!(limiter.global.available_permits(), 3);
+
+        let blocked_limiter = limiter.clone();
+        let blocked_event = event.clone();
+        let blocked = tokio::spawn(async move { blocked_limiter.acquire(&blocked_event).await });
+
+        // Let the blocked task run far enough to start waiting. If acquire()
+        // took the global permit before the per-event one, it would have
+        // already been claimed here even though the task is stuck.
+        tokio::task::yield_now().await;
+        tokio::task::yield_now().await;
+        assert_eq!(limiter.global.available_permits(), 3);
+
+        drop(held);
+        let permits = blocked.await.unwrap().unwrap();
+        assert_eq!(limiter.global.available_permits(), 3);
+        drop(permits);
+    }
+
+    #[test]
+    fn box_error_into_response_preserves_system_error() {
+        let error: BoxError = Box::new(SystemError::new(42, "boom"));
+        let response = box_error_into_response(error);
+        assert_eq!(response, EventResponse::error_with_code(42, "boom"));
+    }
+
+    #[test]
+    fn box_error_into_response_falls_back_for_other_errors() {
+        #[derive(Debug)]
+        struct OtherError;
+
+        impl Display for OtherError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "other error")
+            }
+        }
+
+        impl std::error::Error for OtherError {}
+
+        let error: BoxError = Box::new(OtherError);
+        let response = box_error_into_response(error);
+        assert!(response.is_err());
+        assert_ne!(response, EventResponse::error_with_code(42, "boom"));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::response::EventResponse;
+use std::fmt;
+
+pub type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+#[derive(Debug, Clone)]
+pub struct InternalError {
+    msg: String,
+}
+
+impl InternalError {
+    pub fn new<T: Into<String>>(msg: T) -> Self {
+        Self { msg: msg.into() }
+    }
+
+    pub fn as_response(&self) -> EventResponse {
+        EventResponse::error(self.msg.clone())
+    }
+}
+
+impl fmt::Display for InternalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl std::error::Error for InternalError {}
+
+/// The crate's typed application error, carrying a structured error code
+/// a UI can pattern-match on instead of an opaque message.
+#[derive(Debug, Clone)]
+pub struct SystemError {
+    pub code: i32,
+    pub msg: String,
+}
+
+impl SystemError {
+    pub fn new(code: i32, msg: impl Into<String>) -> Self {
+        Self {
+            code,
+            msg: msg.into(),
+        }
+    }
+}
+
+impl fmt::Display for SystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.msg)
+    }
+}
+
+impl std::error::Error for SystemError {}
+
+impl From<SystemError> for EventResponse {
+    fn from(error: SystemError) -> Self {
+        EventResponse::error_with_code(error.code, error.msg)
+    }
+}
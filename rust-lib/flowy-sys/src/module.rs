@@ -0,0 +1,130 @@
+use crate::{
+    error::SystemError,
+    request::Payload,
+    response::EventResponse,
+    service::{Service, ServiceFactory},
+};
+use futures_core::future::BoxFuture;
+use std::{collections::HashMap, fmt::Display, hash::Hash, sync::Arc};
+use tokio::sync::mpsc;
+
+/// Identifies a single dispatchable action, e.g. `"user:sign_in"`.
+/// `EventDispatch` looks one up in a `ModuleMap` to find the `Module` that
+/// owns it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Event(String);
+
+impl<T> From<T> for Event
+where
+    T: Display,
+{
+    fn from(value: T) -> Self {
+        Event(value.to_string())
+    }
+}
+
+pub type ModuleService = Box<
+    dyn Service<
+            ModuleRequest,
+            Response = EventResponse,
+            Error = SystemError,
+            Future = BoxFuture<'static, Result<EventResponse, SystemError>>,
+        > + Send
+        + Sync,
+>;
+
+pub type ModuleServiceFactory = Box<
+    dyn ServiceFactory<
+            ModuleRequest,
+            Response = EventResponse,
+            Error = SystemError,
+            Service = ModuleService,
+            Future = BoxFuture<'static, Result<ModuleService, SystemError>>,
+        > + Send
+        + Sync,
+>;
+
+pub struct Module {
+    pub name: String,
+    pub event: Event,
+    service_factory: ModuleServiceFactory,
+}
+
+impl Module {
+    pub fn new(name: &str, event: Event, service_factory: ModuleServiceFactory) -> Self {
+        Self {
+            name: name.to_owned(),
+            event,
+            service_factory,
+        }
+    }
+
+    pub fn new_service(&self, cfg: ()) -> BoxFuture<'static, Result<ModuleService, SystemError>> {
+        self.service_factory.new_service(cfg)
+    }
+}
+
+pub type ModuleMap = Arc<HashMap<Event, Module>>;
+
+pub fn as_module_map(modules: Vec<Module>) -> ModuleMap {
+    Arc::new(
+        modules
+            .into_iter()
+            .map(|module| (module.event.clone(), module))
+            .collect(),
+    )
+}
+
+/// The request a module's `Service` actually receives. Built from a
+/// `DispatchRequest` by `DispatchRequest::into_parts`.
+#[derive(Clone)]
+pub struct ModuleRequest {
+    event: Event,
+    id: String,
+    payload: Payload,
+    /// Forwarded from the originating `DispatchRequest` when it was
+    /// dispatched through `EventDispatch::async_send_stream`. A handler
+    /// can push intermediate `EventResponse` chunks onto this sink before
+    /// returning; whatever it returns is pushed as the terminal item by
+    /// `DispatchService::call`.
+    pub(crate) stream_sink: Option<mpsc::UnboundedSender<EventResponse>>,
+}
+
+impl std::fmt::Debug for ModuleRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModuleRequest")
+            .field("event", &self.event)
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl ModuleRequest {
+    pub fn new(event: Event, id: String, payload: Payload) -> Self {
+        Self {
+            event,
+            id,
+            payload,
+            stream_sink: None,
+        }
+    }
+
+    pub fn event(&self) -> Event {
+        self.event.clone()
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn payload(self) -> Payload {
+        self.payload
+    }
+
+    /// The handle a module's `Service::call` implementation can use to
+    /// push intermediate chunks. `None` unless the request came from
+    /// `EventDispatch::async_send_stream`.
+    pub fn stream_sink(&self) -> Option<mpsc::UnboundedSender<EventResponse>> {
+        self.stream_sink.clone()
+    }
+}
@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub enum Payload {
+    None,
+    Bytes(Vec<u8>),
+}
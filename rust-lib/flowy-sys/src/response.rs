@@ -0,0 +1,32 @@
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EventResponse {
+    pub code: i32,
+    pub msg: String,
+}
+
+impl EventResponse {
+    pub fn ok() -> Self {
+        Self {
+            code: 0,
+            msg: String::new(),
+        }
+    }
+
+    pub fn error(msg: impl Into<String>) -> Self {
+        Self {
+            code: -1,
+            msg: msg.into(),
+        }
+    }
+
+    pub fn error_with_code(code: i32, msg: impl Into<String>) -> Self {
+        Self {
+            code,
+            msg: msg.into(),
+        }
+    }
+
+    pub fn is_err(&self) -> bool {
+        self.code != 0
+    }
+}
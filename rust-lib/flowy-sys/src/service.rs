@@ -0,0 +1,23 @@
+use std::future::Future;
+
+/// A unit of async work that turns a `Request` into a `Response`, or an
+/// `Error`. `DispatchService` and every module's handler implement this so
+/// `EventDispatch` and `Layer` can compose them uniformly.
+pub trait Service<Request> {
+    type Response;
+    type Error;
+    type Future: Future<Output = Result<Self::Response, Self::Error>> + Send;
+
+    fn call(&self, req: Request) -> Self::Future;
+}
+
+/// Builds a [`Service`] on demand, so a fresh handler instance can be
+/// constructed per dispatch instead of sharing one long-lived instance.
+pub trait ServiceFactory<Request> {
+    type Response;
+    type Error;
+    type Service: Service<Request, Response = Self::Response, Error = Self::Error>;
+    type Future: Future<Output = Result<Self::Service, Self::Error>> + Send;
+
+    fn new_service(&self, cfg: ()) -> Self::Future;
+}
@@ -0,0 +1,5 @@
+use tokio::runtime::{Builder, Runtime};
+
+pub fn tokio_default_runtime() -> std::io::Result<Runtime> {
+    Builder::new_multi_thread().enable_all().build()
+}